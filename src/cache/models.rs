@@ -0,0 +1,74 @@
+//! Data models for cached problems, questions, and contests
+use crate::cache::parser::MetaData;
+use serde::{Deserialize, Serialize};
+
+/// A single starter-code definition for one language
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CodeDefinition {
+    pub value: String,
+    pub text: String,
+    #[serde(rename = "defaultCode")]
+    pub default_code: String,
+}
+
+/// Acceptance stats for a question
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Stats {
+    #[serde(default)]
+    pub rate: String,
+}
+
+/// A problem, as listed in a category or search result
+#[derive(Debug, Clone)]
+pub struct Problem {
+    pub category: String,
+    pub fid: i32,
+    pub id: i32,
+    pub level: i32,
+    pub locked: bool,
+    pub name: String,
+    pub percent: f32,
+    pub slug: String,
+    pub starred: bool,
+    pub status: String,
+    pub desc: String,
+}
+
+/// Full content and metadata for a single question
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Question {
+    pub content: String,
+    pub stats: Stats,
+    pub defs: Vec<CodeDefinition>,
+    pub case: String,
+    pub all_cases: String,
+    pub metadata: MetaData,
+    pub test: bool,
+    pub t_content: String,
+}
+
+/// A contest question stub, as listed on a contest's info page
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContestQuestionStub {
+    pub id: i32,
+    pub question_id: String,
+    pub credit: i32,
+    pub title: String,
+    pub title_slug: String,
+}
+
+/// A contest, with its questions
+#[derive(Debug, Clone)]
+pub struct Contest {
+    pub id: i32,
+    pub duration: i32,
+    pub start_time: i64,
+    pub title: String,
+    pub title_slug: String,
+    pub description: String,
+    pub is_virtual: bool,
+    pub contains_premium: bool,
+    pub registered: bool,
+    pub questions: Vec<ContestQuestionStub>,
+}
@@ -1,5 +1,6 @@
 //! Sub-Module for parsing resp data
 use super::models::*;
+use crate::plugins::leetcode::Site;
 use serde_json::Value;
 
 /// contest parser
@@ -54,9 +55,9 @@ pub fn problem(problems: &mut Vec<Problem>, v: Value) -> Option<()> {
 
 // TODO: implement test for this
 /// graphql problem && question parser
-pub fn graphql_problem_and_question(v: Value) -> Option<(Problem,Question)> {
+pub fn graphql_problem_and_question(v: Value, site: Site) -> Option<(Problem,Question)> {
     let mut qn = Question::default();
-    assert_eq!(Some(true), desc(&mut qn, v.clone()));
+    assert_eq!(Some(true), desc(&mut qn, v.clone(), site));
     let percent = &qn.stats.rate;
     let percent = percent[..percent.len()-1].parse::<f32>().ok()?;
     let v = v.as_object()?.get("data")?
@@ -82,8 +83,60 @@ pub fn graphql_problem_and_question(v: Value) -> Option<(Problem,Question)> {
     }, qn))
 }
 
+/// a single solution-function parameter, as described by `metaData.params`
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct Param {
+    pub name: String,
+    pub param_type: String,
+}
+
+/// a structured view of a question's `metaData` blob
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct MetaData {
+    pub name: String,
+    pub params: Vec<Param>,
+    pub return_type: String,
+    pub system_design: bool,
+    pub class: Option<String>,
+}
+
+/// metaData parser
+pub fn metadata(v: &Value) -> Option<MetaData> {
+    let o = v.as_object()?;
+    let system_design = o.get("systemdesign").and_then(Value::as_bool).unwrap_or(false);
+    let class = o.get("classname").and_then(Value::as_str).map(str::to_owned);
+
+    // class-based "design" problems (e.g. LRUCache) nest their signature
+    // under the first entry of `methods` rather than exposing `params`/
+    // `return` at the top level
+    let sig = if system_design {
+        o.get("methods")?.as_array()?.first()?.as_object()?
+    } else {
+        o
+    };
+
+    let params = sig
+        .get("params")?.as_array()?
+        .iter()
+        .map(|p| {
+            Some(Param {
+                name: p.get("name")?.as_str()?.to_string(),
+                param_type: p.get("type")?.as_str()?.to_string(),
+            })
+        })
+        .collect::<Option<Vec<Param>>>()?;
+
+    Some(MetaData {
+        name: sig.get("name")?.as_str()?.to_string(),
+        params,
+        return_type: sig.get("return")?.as_object()?.get("type")?.as_str()?.to_string(),
+        system_design,
+        class,
+    })
+}
+
 /// desc parser
-pub fn desc(q: &mut Question, v: Value) -> Option<bool> {
+pub fn desc(q: &mut Question, v: Value, site: Site) -> Option<bool> {
     /* None - parsing failed
      * Some(false) - content was null (premium?)
      * Some(true) - content was parsed
@@ -99,8 +152,18 @@ pub fn desc(q: &mut Question, v: Value) -> Option<bool> {
         return Some(false);
     }
 
+    let t_content = o
+        .get("translatedContent")?
+        .as_str()
+        .unwrap_or("")
+        .to_string();
+
     *q = Question {
-        content: o.get("content")?.as_str().unwrap_or("").to_string(),
+        content: if site == Site::Cn && !t_content.is_empty() {
+            t_content.clone()
+        } else {
+            o.get("content")?.as_str().unwrap_or("").to_string()
+        },
         stats: serde_json::from_str(o.get("stats")?.as_str()?).ok()?,
         defs: serde_json::from_str(o.get("codeDefinition")?.as_str()?).ok()?,
         case: o.get("sampleTestCase")?.as_str()?.to_string(),
@@ -108,18 +171,44 @@ pub fn desc(q: &mut Question, v: Value) -> Option<bool> {
                 .unwrap_or(o.get("sampleTestCase")?) // soft fail to the sampleTestCase
                 .as_str()?
                 .to_string(),
-        metadata: serde_json::from_str(o.get("metaData")?.as_str()?).ok()?,
+        metadata: metadata(&serde_json::from_str(o.get("metaData")?.as_str()?).ok()?)?,
         test: o.get("enableRunCode")?.as_bool()?,
-        t_content: o
-            .get("translatedContent")?
-            .as_str()
-            .unwrap_or("")
-            .to_string(),
+        t_content,
     };
 
     Some(true)
 }
 
+/// a problem's starter code in a single language
+#[derive(Debug, Clone)]
+pub struct CodeSnippet {
+    pub lang_slug: String,
+    pub lang: String,
+    pub code: String,
+}
+
+/// codeSnippets parser
+pub fn languages(v: Value) -> Option<Vec<CodeSnippet>> {
+    trace!("Parse languages...");
+    let arr = v
+        .as_object()?
+        .get("data")?.as_object()?
+        .get("question")?.as_object()?
+        .get("codeSnippets")?.as_array()?;
+
+    let mut res = vec![];
+    for s in arr.iter() {
+        let s = s.as_object()?;
+        res.push(CodeSnippet {
+            lang_slug: s.get("langSlug")?.as_str()?.to_string(),
+            lang: s.get("lang")?.as_str()?.to_string(),
+            code: s.get("code")?.as_str()?.to_string(),
+        });
+    }
+
+    Some(res)
+}
+
 /// tag parser
 pub fn tags(v: Value) -> Option<Vec<String>> {
     trace!("Parse tags...");
@@ -150,6 +239,42 @@ pub fn daily(v: Value) -> Option<i32> {
         .parse().ok()
 }
 
+/// a single entry in a problem's submission history
+#[derive(Debug, Clone)]
+pub struct Submission {
+    pub id: String,
+    pub status_display: String,
+    pub lang: String,
+    pub runtime: String,
+    pub memory: String,
+    pub timestamp: i64,
+}
+
+/// submissionList parser
+pub fn submissions(v: Value) -> Option<Vec<Submission>> {
+    trace!("Parse submissions...");
+    let arr = v
+        .as_object()?
+        .get("data")?.as_object()?
+        .get("submissionList")?.as_object()?
+        .get("submissions")?.as_array()?;
+
+    let mut res = vec![];
+    for s in arr.iter() {
+        let s = s.as_object()?;
+        res.push(Submission {
+            id: s.get("id")?.as_str()?.to_string(),
+            status_display: s.get("statusDisplay")?.as_str()?.to_string(),
+            lang: s.get("lang")?.as_str()?.to_string(),
+            runtime: s.get("runtime")?.as_str()?.to_string(),
+            memory: s.get("memory")?.as_str()?.to_string(),
+            timestamp: s.get("timestamp")?.as_str()?.parse().ok()?,
+        });
+    }
+
+    Some(res)
+}
+
 /// user parser
 pub fn user(v: Value) -> Option<Option<(String,bool)>> {
     // None => error while parsing
@@ -165,6 +290,46 @@ pub fn user(v: Value) -> Option<Option<(String,bool)>> {
     )))
 }
 
+/// a single row of a contest's leaderboard
+#[derive(Debug, Clone)]
+pub struct RankEntry {
+    pub rank: i32,
+    pub username: String,
+    pub score: i32,
+    pub finish_time: i64,
+    /// per-question solve timestamps, ordered by question id
+    pub question_times: Vec<i64>,
+}
+
+/// contest/api/ranking parser
+pub fn ranking(v: Value) -> Option<Vec<RankEntry>> {
+    let o = v.as_object()?;
+    let rows = o.get("total_rank")?.as_array()?;
+    let submissions = o.get("submissions")?.as_array()?;
+
+    rows.iter().zip(submissions.iter()).map(|(row, subs)| {
+        let row = row.as_object()?;
+        let subs = subs.as_object()?;
+
+        let mut question_times: Vec<(i64, i64)> = subs
+            .iter()
+            .map(|(qid, s)| {
+                let s = s.as_object()?;
+                Some((qid.parse().ok()?, s.get("date")?.as_i64()?))
+            })
+            .collect::<Option<Vec<(i64, i64)>>>()?;
+        question_times.sort_by_key(|(qid, _)| *qid);
+
+        Some(RankEntry {
+            rank: row.get("rank")?.as_i64()? as i32,
+            username: row.get("username")?.as_str()?.to_string(),
+            score: row.get("score")?.as_i64()? as i32,
+            finish_time: row.get("finish_time")?.as_i64()?,
+            question_times: question_times.into_iter().map(|(_, t)| t).collect(),
+        })
+    }).collect()
+}
+
 pub use ss::ssr;
 /// string or squence
 mod ss {
@@ -0,0 +1,49 @@
+//! Config loading and persistence
+use crate::{err::Error, plugins::leetcode::Site};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// System-level settings: active site, auth backend, and endpoint URLs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sys {
+    #[serde(default)]
+    pub site: Site,
+    /// which `SessionProvider` to build in `LeetCode::new` ("chrome", "firefox", "env")
+    #[serde(default)]
+    pub session_provider: Option<String>,
+    pub urls: HashMap<String, String>,
+}
+
+/// Cached cookie state, used to detect when the local session has rotated
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Cookies {
+    #[serde(default)]
+    pub csrf: String,
+}
+
+/// On-disk config
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    pub sys: Sys,
+    #[serde(default)]
+    pub cookies: Cookies,
+}
+
+impl Config {
+    /// Persist the current config to disk
+    pub fn sync(&self) -> Result<(), Error> {
+        let toml = toml::to_string(self).map_err(|e| Error::ParseError(e.to_string()))?;
+        std::fs::write(path()?, toml).map_err(|e| Error::ParseError(e.to_string()))?;
+        Ok(())
+    }
+}
+
+/// Locate and parse the on-disk config
+pub fn locate() -> Result<Config, Error> {
+    let content = std::fs::read_to_string(path()?).map_err(|e| Error::ParseError(e.to_string()))?;
+    toml::from_str(&content).map_err(|e| Error::ParseError(e.to_string()))
+}
+
+fn path() -> Result<std::path::PathBuf, Error> {
+    Ok(dirs::home_dir().ok_or(Error::NoneError)?.join(".leetcode").join("leetcode.toml"))
+}
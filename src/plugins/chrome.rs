@@ -0,0 +1,52 @@
+//! Chrome cookie-jar reader
+use crate::err::Error;
+use std::fmt;
+
+/// The session cookie pair lifted from a browser's cookie jar
+#[derive(Debug, Clone, Default)]
+pub struct Cookies {
+    pub session: String,
+    pub csrf: String,
+}
+
+impl fmt::Display for Cookies {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LEETCODE_SESSION={}; csrftoken={};", self.session, self.csrf)
+    }
+}
+
+/// Read the LeetCode session + csrf cookie pair for `domain` out of the
+/// local Chrome profile
+pub fn cookies(domain: &str) -> Result<Cookies, Error> {
+    let db = chrome_cookie_db().ok_or(Error::NoneError)?;
+    let conn = rusqlite::Connection::open(db).map_err(|e| Error::ParseError(e.to_string()))?;
+    let mut stmt = conn
+        .prepare("SELECT name, value FROM cookies WHERE host_key = ?1 OR host_key LIKE ?2")
+        .map_err(|e| Error::ParseError(e.to_string()))?;
+    let mut rows = stmt
+        .query(rusqlite::params![domain, format!("%.{domain}")])
+        .map_err(|e| Error::ParseError(e.to_string()))?;
+
+    let mut session = None;
+    let mut csrf = None;
+    while let Some(row) = rows.next().map_err(|e| Error::ParseError(e.to_string()))? {
+        let name: String = row.get(0).map_err(|e| Error::ParseError(e.to_string()))?;
+        let value: String = row.get(1).map_err(|e| Error::ParseError(e.to_string()))?;
+        match name.as_str() {
+            "LEETCODE_SESSION" => session = Some(value),
+            "csrftoken" => csrf = Some(value),
+            _ => {}
+        }
+    }
+
+    Ok(Cookies {
+        session: session.ok_or(Error::NoneError)?,
+        csrf: csrf.ok_or(Error::NoneError)?,
+    })
+}
+
+/// Finds the default Chrome profile's cookie database
+fn chrome_cookie_db() -> Option<std::path::PathBuf> {
+    let path = dirs::home_dir()?.join(".config/google-chrome/Default/Cookies");
+    path.exists().then_some(path)
+}
@@ -1,5 +1,6 @@
 use self::req::{Json, Mode, Req};
 use crate::{
+    cache::parser::{self, CodeSnippet, RankEntry, Submission},
     cfg::{self, Config},
     err::Error,
     plugins::chrome,
@@ -8,15 +9,31 @@ use reqwest::{
     header::{HeaderMap, HeaderName, HeaderValue},
     Client, ClientBuilder, Response,
 };
+use serde_json::Value;
 use std::{collections::HashMap, str::FromStr, time::Duration};
 use ::function_name::named;
 
+/// Which LeetCode site a client talks to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Site {
+    Com,
+    Cn,
+}
+
+impl Default for Site {
+    fn default() -> Self {
+        Site::Com
+    }
+}
+
 /// LeetCode API set
 #[derive(Clone)]
 pub struct LeetCode {
     pub conf: Config,
     client: Client,
     default_headers: HeaderMap,
+    site: Site,
 }
 
 macro_rules! make_req {
@@ -44,14 +61,29 @@ impl LeetCode {
     /// New LeetCode client
     pub fn new() -> Result<LeetCode, crate::Error> {
         let conf = cfg::locate()?;
-        let cookies = chrome::cookies()?;
-        let default_headers = LeetCode::headers(
+        let site = conf.sys.site;
+
+        let provider: Box<dyn session::SessionProvider> = match conf.sys.session_provider.as_deref() {
+            Some("firefox") => Box::new(session::FirefoxSession::default()),
+            Some("env") => Box::new(session::StaticSession::from_env()?),
+            _ => Box::new(session::ChromeSession::default()),
+        };
+        let session_headers = provider.session_headers(site)?;
+        let csrf = session_headers
+            .iter()
+            .find(|(k, _)| k == "x-csrftoken")
+            .map(|(_, v)| v.to_owned())
+            .ok_or(Error::NoneError)?;
+
+        let mut headers = LeetCode::headers(
             HeaderMap::new(),
+            session_headers.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect(),
+        )?;
+        headers = LeetCode::headers(
+            headers,
             vec![
-                ("Cookie", cookies.to_string().as_str()),
-                ("x-csrftoken", &cookies.csrf),
                 ("x-requested-with", "XMLHttpRequest"),
-                ("Origin", &conf.sys.urls["base"]),
+                ("Origin", &LeetCode::site_url(&conf, site, "base")?),
             ],
         )?;
 
@@ -61,22 +93,43 @@ impl LeetCode {
             .build()?;
 
         // Sync conf
-        if conf.cookies.csrf != cookies.csrf {
+        if conf.cookies.csrf != csrf {
             conf.sync()?;
         }
 
         Ok(LeetCode {
             conf,
             client,
-            default_headers,
+            default_headers: headers,
+            site,
         })
     }
 
+    /// Resolve a URL template for the active site
+    ///
+    /// Site-specific overrides live under a `$key_cn`-suffixed entry in
+    /// `conf.sys.urls`; when the active site has no override the default
+    /// `$key` entry is used.
+    fn site_url(conf: &Config, site: Site, key: &str) -> Result<String, Error> {
+        if site == Site::Cn {
+            if let Some(u) = conf.sys.urls.get(&format!("{}_cn", key)) {
+                return Ok(u.to_owned());
+            }
+        }
+
+        conf.sys.urls.get(key).map(|u| u.to_owned()).ok_or(Error::NoneError)
+    }
+
+    /// Resolve a URL template for the active site
+    fn url(&self, key: &str) -> Result<String, Error> {
+        LeetCode::site_url(&self.conf, self.site, key)
+    }
+
     /// Generic GraphQL query
     #[named]
     pub async fn get_graphql(&self, query: String, variables: Option<String>) -> Result<Response, Error> {
-        let url = &self.conf.sys.urls.get("graphql").ok_or(Error::NoneError)?;
-        let refer = self.conf.sys.urls.get("base").ok_or(Error::NoneError)?;
+        let url = self.url("graphql")?;
+        let refer = self.url("base")?;
         let mut json: Json = HashMap::new();
         json.insert("operationName", "a".to_string());
         if let Some(v) = variables {
@@ -84,9 +137,9 @@ impl LeetCode {
         }
         json.insert("query", query);
 
-        let mut req = make_req!(self, url.to_string());
+        let mut req = make_req!(self, url);
         req.mode = Mode::Post(json);
-        req.refer = Some(refer.to_string());
+        req.refer = Some(refer);
         req
         .send(&self.client)
         .await
@@ -96,14 +149,9 @@ impl LeetCode {
     #[named]
     pub async fn get_category_problems(&self, category: &str) -> Result<Response, Error> {
         trace!("Requesting {} problems...", &category);
-        let url = &self
-            .conf
-            .sys
-            .urls
-            .get("problems").ok_or(Error::NoneError)?
-            .replace("$category", category);
-
-        make_req!(self, url.to_string())
+        let url = self.url("problems")?.replace("$category", category);
+
+        make_req!(self, url)
         .send(&self.client)
         .await
     }
@@ -148,9 +196,7 @@ impl LeetCode {
     /// Register for a contest
     #[named]
     pub async fn register_contest(&self, contest: &str) -> Result<Response,Error> {
-        let url = self.conf.sys.urls.get("contest_register")
-            .ok_or(Error::NoneError)?
-            .replace("$contest_slug", contest);
+        let url = self.url("contest_register")?.replace("$contest_slug", contest);
         let mut req = make_req!(self, url);
         req.mode = Mode::Post(HashMap::new());
         req
@@ -163,15 +209,45 @@ impl LeetCode {
     pub async fn get_contest_info(&self, contest: &str) -> Result<Response, Error> {
         trace!("Requesting {} detail...", contest);
         // cannot use the graphql API here because it does not provide registration status
-        let url = &self.conf.sys.urls
-            .get("contest_info")
-            .ok_or(Error::NoneError)?
-            .replace("$contest_slug", contest);
-        make_req!(self, url.to_string())
+        let url = self.url("contest_info")?.replace("$contest_slug", contest);
+        make_req!(self, url)
+        .send(&self.client)
+        .await
+    }
+
+    /// Get a page of a contest's post-contest leaderboard
+    #[named]
+    pub async fn get_contest_ranking(&self, contest: &str, page: i32) -> Result<Response, Error> {
+        let url = self.url("contest_ranking")?
+            .replace("$contest_slug", contest)
+            .replace("$page", &page.to_string());
+        make_req!(self, url)
         .send(&self.client)
         .await
     }
 
+    /// Resolve the current user's row in a contest's leaderboard, paging
+    /// through `get_contest_ranking` until it's found
+    pub async fn my_contest_rank(&self, contest: &str) -> Result<Option<RankEntry>, Error> {
+        let me = parser::user(self.get_user_info().await?.json().await?)
+            .ok_or(Error::NoneError)?
+            .ok_or(Error::NoneError)?
+            .0;
+
+        let mut page = 1;
+        loop {
+            let v: Value = self.get_contest_ranking(contest, page).await?.json().await?;
+            let entries = parser::ranking(v).ok_or(Error::NoneError)?;
+            if entries.is_empty() {
+                return Ok(None);
+            }
+            if let Some(found) = entries.into_iter().find(|e| e.username == me) {
+                return Ok(Some(found));
+            }
+            page += 1;
+        }
+    }
+
     /// Get full question detail
     pub async fn get_question_detail(&self, problem: &str) -> Result<Response,Error> {
         self.get_graphql("query a($s: String!) {
@@ -205,6 +281,12 @@ impl LeetCode {
     }
 
 
+    /// Get the languages (and their starter code) LeetCode offers for a problem
+    pub async fn get_languages(&self, problem: &str) -> Result<Vec<CodeSnippet>, Error> {
+        let v: Value = self.get_question_detail(problem).await?.json().await?;
+        parser::languages(v).ok_or(Error::NoneError)
+    }
+
     /// Send code to judge
     #[named]
     pub async fn run_code(&self, j: Json, url: String, refer: String) -> Result<Response, Error> {
@@ -220,11 +302,68 @@ impl LeetCode {
     /// Get the result of submission / testing
     #[named]
     pub async fn verify_result(&self, id: String) -> Result<Response, Error> {
-        let url = self.conf.sys.urls.get("verify").ok_or(Error::NoneError)?.replace("$id", &id);
+        let url = self.url("verify")?.replace("$id", &id);
         make_req!(self, url)
         .send(&self.client)
         .await
     }
+
+    /// Get a problem's submission history
+    pub async fn get_submission_list(&self, slug: &str, limit: i32, offset: i32) -> Result<Response, Error> {
+        self.get_graphql(
+            "query a($slug: String!, $limit: Int!, $offset: Int!) {
+               submissionList(questionSlug: $slug, limit: $limit, offset: $offset) {
+                 submissions {
+                   id
+                   statusDisplay
+                   lang
+                   runtime
+                   memory
+                   timestamp
+                 }
+               }
+             }".to_owned(),
+            Some(
+                r#"{"slug": "$slug", "limit": $limit, "offset": $offset}"#
+                    .replace("$slug", slug)
+                    .replace("$limit", &limit.to_string())
+                    .replace("$offset", &offset.to_string()),
+            ),
+        ).await
+    }
+
+    /// Poll a problem's submission history until the latest submission
+    /// reaches a terminal verdict, returning every status transition seen
+    /// (e.g. "Pending" -> "Accepted")
+    pub async fn watch_submissions(&self, slug: &str) -> Result<Vec<Submission>, Error> {
+        const MAX_ATTEMPTS: u32 = 20;
+        const MAX_DELAY: Duration = Duration::from_secs(30);
+
+        let mut transitions = vec![];
+        let mut last_status: Option<String> = None;
+        let mut delay = Duration::from_secs(1);
+
+        for _ in 0..MAX_ATTEMPTS {
+            let v: Value = self.get_submission_list(slug, 1, 0).await?.json().await?;
+            let latest = parser::submissions(v).ok_or(Error::NoneError)?.into_iter().next();
+
+            if let Some(submission) = latest {
+                if last_status.as_deref() != Some(submission.status_display.as_str()) {
+                    last_status = Some(submission.status_display.clone());
+                    let terminal = !matches!(submission.status_display.as_str(), "Pending" | "Judging" | "Compiling");
+                    transitions.push(submission);
+                    if terminal {
+                        return Ok(transitions);
+                    }
+                }
+            }
+
+            tokio::time::sleep(delay).await;
+            delay = std::cmp::min(delay * 2, MAX_DELAY);
+        }
+
+        Ok(transitions)
+    }
 }
 
 /// Sub-module for leetcode, simplify requests
@@ -274,3 +413,112 @@ mod req {
         }
     }
 }
+
+/// Sub-module for leetcode, pluggable auth backends
+mod session {
+    use super::Site;
+    use crate::{err::Error, plugins::chrome};
+
+    /// Supplies the `Cookie`/`x-csrftoken` header pair needed to authenticate
+    /// requests to LeetCode
+    pub trait SessionProvider {
+        fn session_headers(&self, site: Site) -> Result<Vec<(String, String)>, Error>;
+    }
+
+    /// The cookie domain a site's session is scoped to
+    fn cookie_domain(site: Site) -> &'static str {
+        match site {
+            Site::Com => "leetcode.com",
+            Site::Cn => "leetcode.cn",
+        }
+    }
+
+    /// Reads the LeetCode session cookie out of the local Chrome profile
+    #[derive(Default)]
+    pub struct ChromeSession;
+
+    impl SessionProvider for ChromeSession {
+        fn session_headers(&self, site: Site) -> Result<Vec<(String, String)>, Error> {
+            let cookies = chrome::cookies(cookie_domain(site))?;
+            Ok(vec![
+                ("Cookie".to_string(), cookies.to_string()),
+                ("x-csrftoken".to_string(), cookies.csrf.clone()),
+            ])
+        }
+    }
+
+    /// Reads the LeetCode session cookie out of the local Firefox profile
+    #[derive(Default)]
+    pub struct FirefoxSession;
+
+    impl SessionProvider for FirefoxSession {
+        fn session_headers(&self, site: Site) -> Result<Vec<(String, String)>, Error> {
+            let host = cookie_domain(site);
+            let db = firefox_cookie_db().ok_or(Error::NoneError)?;
+            let conn = rusqlite::Connection::open(db).map_err(|e| Error::ParseError(e.to_string()))?;
+            let mut stmt = conn
+                .prepare("SELECT name, value FROM moz_cookies WHERE host = ?1 OR host LIKE ?2")
+                .map_err(|e| Error::ParseError(e.to_string()))?;
+            let mut rows = stmt
+                .query(rusqlite::params![host, format!("%.{host}")])
+                .map_err(|e| Error::ParseError(e.to_string()))?;
+
+            let mut session = None;
+            let mut csrf = None;
+            while let Some(row) = rows.next().map_err(|e| Error::ParseError(e.to_string()))? {
+                let name: String = row.get(0).map_err(|e| Error::ParseError(e.to_string()))?;
+                let value: String = row.get(1).map_err(|e| Error::ParseError(e.to_string()))?;
+                match name.as_str() {
+                    "LEETCODE_SESSION" => session = Some(value),
+                    "csrftoken" => csrf = Some(value),
+                    _ => {}
+                }
+            }
+
+            let session = session.ok_or(Error::NoneError)?;
+            let csrf = csrf.ok_or(Error::NoneError)?;
+            Ok(vec![
+                ("Cookie".to_string(), format!("LEETCODE_SESSION={session}; csrftoken={csrf};")),
+                ("x-csrftoken".to_string(), csrf),
+            ])
+        }
+    }
+
+    /// Finds the most recently used Firefox profile's cookie database
+    fn firefox_cookie_db() -> Option<std::path::PathBuf> {
+        let root = dirs::home_dir()?.join(".mozilla/firefox");
+        std::fs::read_dir(root)
+            .ok()?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path().join("cookies.sqlite"))
+            .filter(|p| p.exists())
+            .max_by_key(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok())
+    }
+
+    /// Reads a raw `LEETCODE_SESSION`/csrftoken pair directly, for headless
+    /// or containerized use where no browser is available
+    #[derive(Default, Clone)]
+    pub struct StaticSession {
+        pub session: String,
+        pub csrf: String,
+    }
+
+    impl StaticSession {
+        /// Build a provider from the `LEETCODE_SESSION`/`LEETCODE_CSRFTOKEN` env vars
+        pub fn from_env() -> Result<Self, Error> {
+            Ok(StaticSession {
+                session: std::env::var("LEETCODE_SESSION").map_err(|_| Error::NoneError)?,
+                csrf: std::env::var("LEETCODE_CSRFTOKEN").map_err(|_| Error::NoneError)?,
+            })
+        }
+    }
+
+    impl SessionProvider for StaticSession {
+        fn session_headers(&self, _site: Site) -> Result<Vec<(String, String)>, Error> {
+            Ok(vec![
+                ("Cookie".to_string(), format!("LEETCODE_SESSION={}; csrftoken={};", self.session, self.csrf)),
+                ("x-csrftoken".to_string(), self.csrf.clone()),
+            ])
+        }
+    }
+}